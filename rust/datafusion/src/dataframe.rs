@@ -19,8 +19,10 @@
 
 use crate::arrow::record_batch::RecordBatch;
 use crate::error::Result;
-use crate::logicalplan::{Expr, LogicalPlan};
+use crate::logicalplan::{Expr, JoinType, LogicalPlan, Partitioning};
 use arrow::datatypes::Schema;
+use async_trait::async_trait;
+use parquet::file::properties::WriterProperties;
 use std::sync::Arc;
 
 /// DataFrame represents a logical set of rows with the same named columns.
@@ -49,7 +51,8 @@ use std::sync::Arc;
 /// # Ok(())
 /// # }
 /// ```
-pub trait DataFrame {
+#[async_trait]
+pub trait DataFrame: Send + Sync {
     /// Filter the DataFrame by column. Returns a new DataFrame only containing the
     /// specified columns.
     ///
@@ -123,6 +126,65 @@ pub trait DataFrame {
         aggr_expr: Vec<Expr>,
     ) -> Result<Arc<dyn DataFrame>>;
 
+    /// Join this DataFrame with another DataFrame using the specified columns as join keys.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # use datafusion::logicalplan::JoinType;
+    /// # fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let left = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let right = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let join = left.join(right, JoinType::Inner, &["a", "b"], &["a", "b"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn join(
+        &self,
+        right: Arc<dyn DataFrame>,
+        join_type: JoinType,
+        left_cols: &[&str],
+        right_cols: &[&str],
+    ) -> Result<Arc<dyn DataFrame>>;
+
+    /// Calculate the union of two DataFrames, preserving duplicate rows. The two DataFrames must
+    /// have schemas that are compatible (same number of fields and matching data types).
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let d2 = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let df = df.union(d2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn union(&self, dataframe: Arc<dyn DataFrame>) -> Result<Arc<dyn DataFrame>>;
+
+    /// Repartition a DataFrame based on a logical partitioning scheme.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # use datafusion::logicalplan::{col, Partitioning};
+    /// # fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let df1 = df.repartition(Partitioning::Hash(vec![col("a")], 16))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn repartition(
+        &self,
+        partitioning_scheme: Partitioning,
+    ) -> Result<Arc<dyn DataFrame>>;
+
     /// Limit the number of rows returned from this DataFrame.
     ///
     /// ```
@@ -163,14 +225,105 @@ pub trait DataFrame {
     /// # use datafusion::error::Result;
     /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
     /// # use datafusion::logicalplan::col;
-    /// # fn main() -> Result<()> {
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let batches = df.collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn collect(&self) -> Result<Vec<RecordBatch>>;
+
+    /// Executes this DataFrame and collects all results into a vector of vector of RecordBatch
+    /// maintaining the input partitioning.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # use datafusion::logicalplan::col;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let batches = df.collect_partitioned().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn collect_partitioned(&self) -> Result<Vec<Vec<RecordBatch>>>;
+
+    /// Execute this DataFrame and write the results to CSV files, one file per output partition,
+    /// under the given directory (`part-0.csv`, `part-1.csv`, ...). Partitions are streamed to
+    /// disk so the full result is never buffered in memory.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// df.write_csv("out").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn write_csv(&self, path: &str) -> Result<()>;
+
+    /// Execute this DataFrame and write the results to Parquet files, one file per output
+    /// partition, under the given directory (`part-0.parquet`, `part-1.parquet`, ...). Partitions
+    /// are streamed to disk so the full result is never buffered in memory.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
     /// let mut ctx = ExecutionContext::new();
     /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
-    /// let batches = df.collect()?;
+    /// df.write_parquet("out", None).await?;
     /// # Ok(())
     /// # }
     /// ```
-    fn collect(&self) -> Result<Vec<RecordBatch>>;
+    async fn write_parquet(
+        &self,
+        path: &str,
+        writer_properties: Option<WriterProperties>,
+    ) -> Result<()>;
+
+    /// Print results.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// df.show().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn show(&self) -> Result<()>;
+
+    /// Print results and limit rows.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// df.show_limit(10).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn show_limit(&self, num: usize) -> Result<()>;
 
     /// Returns the schema describing the output of this DataFrame in terms of columns returned,
     /// where each column has a name, data type, and nullability attribute.
@@ -192,6 +345,42 @@ pub trait DataFrame {
     /// Return the logical plan represented by this DataFrame.
     fn to_logical_plan(&self) -> LogicalPlan;
 
+    /// Unparse the logical plan built by this DataFrame back into a standard SQL `SELECT`
+    /// statement. This is useful for handing a programmatically-built pipeline off to an external
+    /// SQL system, or for inspecting it as SQL while debugging.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # use datafusion::logicalplan::col;
+    /// # fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let sql = df.select_columns(vec!["a", "b"])?.to_sql()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_sql(&self) -> Result<String>;
+
+    /// Return a DataFrame with the explanation of its plan so far.
+    ///
+    /// if `verbose` is true, more details will be included.
+    ///
+    /// ```
+    /// # use datafusion::ExecutionContext;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::execution::physical_plan::csv::CsvReadOptions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let mut ctx = ExecutionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new())?;
+    /// let batches = df.limit(100)?.explain(false)?.collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn explain(&self, verbose: bool) -> Result<Arc<dyn DataFrame>>;
+
     /// Create an expression to represent the min() aggregate function
     fn min(&self, expr: Expr) -> Result<Expr>;
 
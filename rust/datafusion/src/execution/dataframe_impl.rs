@@ -0,0 +1,239 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Concrete [`DataFrame`] implementation backed by a [`LogicalPlan`] and the
+//! [`ExecutionContextState`] it was built against.
+
+use std::fs::File;
+use std::sync::{Arc, Mutex};
+
+use arrow::datatypes::Schema;
+use async_trait::async_trait;
+use futures::StreamExt;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+
+use crate::arrow::csv;
+use crate::arrow::record_batch::RecordBatch;
+use crate::arrow::util::pretty;
+use crate::dataframe::DataFrame;
+use crate::error::{ExecutionError, Result};
+use crate::execution::context::{ExecutionContext, ExecutionContextState};
+use crate::logicalplan::{JoinType, LogicalPlan, LogicalPlanBuilder, Partitioning};
+use crate::plan_to_sql::plan_to_sql;
+
+/// Default [`DataFrame`] implementation. Wraps a [`LogicalPlan`] together with the context it
+/// will be planned and executed against; every transformation method returns a new
+/// `DataFrameImpl` around an extended plan rather than mutating this one.
+pub struct DataFrameImpl {
+    ctx_state: Arc<Mutex<ExecutionContextState>>,
+    plan: LogicalPlan,
+}
+
+impl DataFrameImpl {
+    /// Build a `DataFrameImpl` for `plan`, to be planned and executed against `ctx_state`.
+    pub fn new(ctx_state: Arc<Mutex<ExecutionContextState>>, plan: &LogicalPlan) -> Self {
+        Self {
+            ctx_state,
+            plan: plan.clone(),
+        }
+    }
+
+    /// Wrap `plan` as a new DataFrame sharing this one's execution context.
+    fn with_plan(&self, plan: LogicalPlan) -> Arc<dyn DataFrame> {
+        Arc::new(Self::new(self.ctx_state.clone(), &plan))
+    }
+
+    /// Build an execution context that shares the state this DataFrame was built against.
+    fn context(&self) -> ExecutionContext {
+        ExecutionContext::from(self.ctx_state.clone())
+    }
+}
+
+#[async_trait]
+impl DataFrame for DataFrameImpl {
+    fn join(
+        &self,
+        right: Arc<dyn DataFrame>,
+        join_type: JoinType,
+        left_cols: &[&str],
+        right_cols: &[&str],
+    ) -> Result<Arc<dyn DataFrame>> {
+        if left_cols.len() != right_cols.len() {
+            return Err(ExecutionError::General(format!(
+                "join requires the same number of columns on both sides, found {} left and {} right",
+                left_cols.len(),
+                right_cols.len()
+            )));
+        }
+        let left_schema = self.schema();
+        for c in left_cols {
+            left_schema.field_with_name(c).map_err(|_| {
+                ExecutionError::General(format!(
+                    "Column '{}' not found on left side of join",
+                    c
+                ))
+            })?;
+        }
+        let right_schema = right.schema();
+        for c in right_cols {
+            right_schema.field_with_name(c).map_err(|_| {
+                ExecutionError::General(format!(
+                    "Column '{}' not found on right side of join",
+                    c
+                ))
+            })?;
+        }
+        let right_plan = right.to_logical_plan();
+        let plan = LogicalPlanBuilder::from(&self.plan)
+            .join(&right_plan, join_type, left_cols, right_cols)?
+            .build()?;
+        Ok(self.with_plan(plan))
+    }
+
+    fn union(&self, dataframe: Arc<dyn DataFrame>) -> Result<Arc<dyn DataFrame>> {
+        let left_fields = self.schema().fields();
+        let right_fields = dataframe.schema().fields();
+        if left_fields.len() != right_fields.len() {
+            return Err(ExecutionError::General(format!(
+                "union requires both DataFrames to have the same number of fields, found {} and {}",
+                left_fields.len(),
+                right_fields.len()
+            )));
+        }
+        for (left_field, right_field) in left_fields.iter().zip(right_fields.iter()) {
+            if left_field.data_type() != right_field.data_type() {
+                return Err(ExecutionError::General(format!(
+                    "union requires matching schemas, but field '{}' ({:?}) does not match field '{}' ({:?})",
+                    left_field.name(),
+                    left_field.data_type(),
+                    right_field.name(),
+                    right_field.data_type()
+                )));
+            }
+        }
+        let right_plan = dataframe.to_logical_plan();
+        let plan = LogicalPlanBuilder::from(&self.plan)
+            .union(right_plan)?
+            .build()?;
+        Ok(self.with_plan(plan))
+    }
+
+    fn repartition(&self, partitioning_scheme: Partitioning) -> Result<Arc<dyn DataFrame>> {
+        let partition_count = match &partitioning_scheme {
+            Partitioning::RoundRobinBatch(n) => *n,
+            Partitioning::Hash(_, n) => *n,
+        };
+        if partition_count == 0 {
+            return Err(ExecutionError::General(
+                "repartition requires a non-zero partition count".to_string(),
+            ));
+        }
+        let plan = LogicalPlanBuilder::from(&self.plan)
+            .repartition(partitioning_scheme)?
+            .build()?;
+        Ok(self.with_plan(plan))
+    }
+
+    async fn collect(&self) -> Result<Vec<RecordBatch>> {
+        let physical_plan = self.context().create_physical_plan(&self.plan)?;
+        crate::execution::physical_plan::collect(physical_plan).await
+    }
+
+    async fn collect_partitioned(&self) -> Result<Vec<Vec<RecordBatch>>> {
+        let physical_plan = self.context().create_physical_plan(&self.plan)?;
+        let partition_count = physical_plan.output_partitioning().partition_count();
+        let mut partitions = Vec::with_capacity(partition_count);
+        for i in 0..partition_count {
+            let mut stream = physical_plan.execute(i).await?;
+            let mut batches = vec![];
+            while let Some(batch) = stream.next().await {
+                batches.push(batch?);
+            }
+            partitions.push(batches);
+        }
+        Ok(partitions)
+    }
+
+    async fn write_csv(&self, path: &str) -> Result<()> {
+        let physical_plan = self.context().create_physical_plan(&self.plan)?;
+        std::fs::create_dir_all(path)?;
+        for i in 0..physical_plan.output_partitioning().partition_count() {
+            let mut stream = physical_plan.execute(i).await?;
+            let file = File::create(format!("{}/part-{}.csv", path, i))?;
+            let mut writer = csv::Writer::new(file);
+            while let Some(batch) = stream.next().await {
+                writer.write(&batch?)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn write_parquet(
+        &self,
+        path: &str,
+        writer_properties: Option<WriterProperties>,
+    ) -> Result<()> {
+        let physical_plan = self.context().create_physical_plan(&self.plan)?;
+        std::fs::create_dir_all(path)?;
+        for i in 0..physical_plan.output_partitioning().partition_count() {
+            let mut stream = physical_plan.execute(i).await?;
+            let file = File::create(format!("{}/part-{}.parquet", path, i))?;
+            let mut writer =
+                ArrowWriter::try_new(file, physical_plan.schema(), writer_properties.clone())?;
+            while let Some(batch) = stream.next().await {
+                writer.write(&batch?)?;
+            }
+            writer.close()?;
+        }
+        Ok(())
+    }
+
+    fn limit(&self, n: usize) -> Result<Arc<dyn DataFrame>> {
+        let plan = LogicalPlanBuilder::from(&self.plan).limit(n)?.build()?;
+        Ok(self.with_plan(plan))
+    }
+
+    async fn show(&self) -> Result<()> {
+        let batches = self.collect().await?;
+        Ok(pretty::print_batches(&batches)?)
+    }
+
+    async fn show_limit(&self, num: usize) -> Result<()> {
+        let batches = self.limit(num)?.collect().await?;
+        Ok(pretty::print_batches(&batches)?)
+    }
+
+    fn explain(&self, verbose: bool) -> Result<Arc<dyn DataFrame>> {
+        let plan = LogicalPlanBuilder::from(&self.plan)
+            .explain(verbose)?
+            .build()?;
+        Ok(self.with_plan(plan))
+    }
+
+    fn schema(&self) -> &Schema {
+        self.plan.schema()
+    }
+
+    fn to_logical_plan(&self) -> LogicalPlan {
+        self.plan.clone()
+    }
+
+    fn to_sql(&self) -> Result<String> {
+        plan_to_sql(&self.plan)
+    }
+}
@@ -0,0 +1,260 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Unparse a [`LogicalPlan`] back into a SQL `SELECT` statement.
+//!
+//! The entry point is [`plan_to_sql`], which folds the plan tree into a single [`SelectBuilder`]
+//! and renders it. Only the subset of nodes produced by the DataFrame transformations is
+//! supported; anything else yields a `NotImplemented` error rather than emitting incorrect SQL.
+
+use crate::error::{ExecutionError, Result};
+use crate::logicalplan::{Expr, LogicalPlan, Operator, ScalarValue};
+
+/// Accumulates the clauses of a single `SELECT` statement while the plan tree is walked bottom-up.
+#[derive(Default)]
+struct SelectBuilder {
+    projection: Vec<String>,
+    from: Option<String>,
+    selection: Option<String>,
+    group_by: Vec<String>,
+    order_by: Vec<String>,
+    limit: Option<usize>,
+}
+
+impl SelectBuilder {
+    /// Render the accumulated clauses into a SQL string.
+    fn build(&self) -> String {
+        let projection = if self.projection.is_empty() {
+            "*".to_string()
+        } else {
+            self.projection.join(", ")
+        };
+        let mut sql = format!("SELECT {}", projection);
+        if let Some(from) = &self.from {
+            sql.push_str(&format!(" FROM {}", from));
+        }
+        if let Some(selection) = &self.selection {
+            sql.push_str(&format!(" WHERE {}", selection));
+        }
+        if !self.group_by.is_empty() {
+            sql.push_str(&format!(" GROUP BY {}", self.group_by.join(", ")));
+        }
+        if !self.order_by.is_empty() {
+            sql.push_str(&format!(" ORDER BY {}", self.order_by.join(", ")));
+        }
+        if let Some(limit) = self.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+        sql
+    }
+}
+
+/// Unparse a [`LogicalPlan`] into a SQL `SELECT` statement.
+pub fn plan_to_sql(plan: &LogicalPlan) -> Result<String> {
+    let mut builder = SelectBuilder::default();
+    fold_plan(plan, &mut builder)?;
+    Ok(builder.build())
+}
+
+/// Fold a plan node and its inputs into `builder`.
+fn fold_plan(plan: &LogicalPlan, builder: &mut SelectBuilder) -> Result<()> {
+    match plan {
+        LogicalPlan::TableScan { table_name, .. } => {
+            builder.from = Some(table_name.clone());
+            Ok(())
+        }
+        LogicalPlan::Projection { expr, input, .. } => {
+            fold_plan(input, builder)?;
+            // A Projection sitting above an Aggregate carries the group + aggregate expressions;
+            // render it as the select list (aggregates included) in either case.
+            builder.projection = expr.iter().map(expr_to_sql).collect::<Result<_>>()?;
+            Ok(())
+        }
+        LogicalPlan::Filter { predicate, input } => {
+            fold_plan(input, builder)?;
+            builder.selection = Some(expr_to_sql(predicate)?);
+            Ok(())
+        }
+        LogicalPlan::Aggregate {
+            input,
+            group_expr,
+            aggr_expr,
+            ..
+        } => {
+            fold_plan(input, builder)?;
+            builder.group_by = group_expr.iter().map(expr_to_sql).collect::<Result<_>>()?;
+            let mut projection = builder.group_by.clone();
+            for e in aggr_expr {
+                projection.push(expr_to_sql(e)?);
+            }
+            builder.projection = projection;
+            Ok(())
+        }
+        LogicalPlan::Sort { expr, input } => {
+            fold_plan(input, builder)?;
+            builder.order_by = expr.iter().map(expr_to_sql).collect::<Result<_>>()?;
+            Ok(())
+        }
+        LogicalPlan::Limit { n, input } => {
+            fold_plan(input, builder)?;
+            builder.limit = Some(*n);
+            Ok(())
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported plan node in SQL unparser: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Reconstruct SQL text for a single expression.
+fn expr_to_sql(expr: &Expr) -> Result<String> {
+    match expr {
+        Expr::Column(name) => Ok(name.clone()),
+        Expr::Literal(value) => literal_to_sql(value),
+        Expr::BinaryExpr { left, op, right } => Ok(format!(
+            "{} {} {}",
+            expr_to_sql(left)?,
+            operator_to_sql(op)?,
+            expr_to_sql(right)?
+        )),
+        Expr::Sort {
+            expr,
+            asc,
+            nulls_first,
+        } => {
+            let dir = if *asc { "ASC" } else { "DESC" };
+            let nulls = if *nulls_first {
+                "NULLS FIRST"
+            } else {
+                "NULLS LAST"
+            };
+            Ok(format!("{} {} {}", expr_to_sql(expr)?, dir, nulls))
+        }
+        Expr::AggregateFunction { name, args } => {
+            let args = args
+                .iter()
+                .map(expr_to_sql)
+                .collect::<Result<Vec<_>>>()?
+                .join(", ");
+            Ok(format!("{}({})", name.to_uppercase(), args))
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported expression in SQL unparser: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Render a binary operator as its SQL token.
+fn operator_to_sql(op: &Operator) -> Result<&'static str> {
+    Ok(match op {
+        Operator::Eq => "=",
+        Operator::NotEq => "!=",
+        Operator::Lt => "<",
+        Operator::LtEq => "<=",
+        Operator::Gt => ">",
+        Operator::GtEq => ">=",
+        Operator::Plus => "+",
+        Operator::Minus => "-",
+        Operator::Multiply => "*",
+        Operator::Divide => "/",
+        Operator::And => "AND",
+        Operator::Or => "OR",
+        other => {
+            return Err(ExecutionError::NotImplemented(format!(
+                "Unsupported operator in SQL unparser: {:?}",
+                other
+            )))
+        }
+    })
+}
+
+/// Render a scalar literal as SQL, quoting strings.
+fn literal_to_sql(value: &ScalarValue) -> Result<String> {
+    Ok(match value {
+        ScalarValue::Boolean(Some(b)) => b.to_string(),
+        ScalarValue::Int64(Some(i)) => i.to_string(),
+        ScalarValue::Float64(Some(f)) => f.to_string(),
+        ScalarValue::Utf8(Some(s)) => format!("'{}'", s.replace('\'', "''")),
+        ScalarValue::Null
+        | ScalarValue::Boolean(None)
+        | ScalarValue::Int64(None)
+        | ScalarValue::Float64(None)
+        | ScalarValue::Utf8(None) => "NULL".to_string(),
+        other => {
+            return Err(ExecutionError::NotImplemented(format!(
+                "Unsupported literal in SQL unparser: {:?}",
+                other
+            )))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logicalplan::{col, LogicalPlanBuilder};
+    use arrow::datatypes::{DataType, Field};
+
+    fn test_schema() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, false),
+        ]
+    }
+
+    #[test]
+    fn aggregate_sort_limit() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty("t", &test_schema(), None)?
+            .aggregate(
+                vec![col("a")],
+                vec![Expr::AggregateFunction {
+                    name: "MIN".to_string(),
+                    args: vec![col("b")],
+                }],
+            )?
+            .sort(vec![col("a").sort(true, true)])?
+            .limit(10)?
+            .build()?;
+        assert_eq!(
+            plan_to_sql(&plan)?,
+            "SELECT a, MIN(b) FROM t GROUP BY a ORDER BY a ASC NULLS FIRST LIMIT 10"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn filter_and_projection() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty("t", &test_schema(), None)?
+            .filter(col("a").eq(col("b")))?
+            .project(vec![col("a")])?
+            .build()?;
+        assert_eq!(plan_to_sql(&plan)?, "SELECT a FROM t WHERE a = b");
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_operator_errors_instead_of_emitting_questionmark() {
+        let expr = Expr::BinaryExpr {
+            left: Box::new(col("a")),
+            op: Operator::Modulus,
+            right: Box::new(col("b")),
+        };
+        assert!(expr_to_sql(&expr).is_err());
+    }
+}
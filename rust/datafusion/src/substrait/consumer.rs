@@ -0,0 +1,488 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reconstruct a DataFusion [`LogicalPlan`] from a Substrait `Plan`.
+
+use std::collections::HashMap;
+
+use arrow::datatypes::{DataType, Field, Schema};
+
+use crate::error::{ExecutionError, Result};
+use crate::logicalplan::{
+    Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Operator, ScalarValue,
+};
+
+use substrait::protobuf::{
+    expression::{literal::LiteralType, RexType},
+    extensions::simple_extension_declaration::MappingType,
+    function_argument::ArgType,
+    plan_rel::RelType as PlanRelType,
+    r#type::{Kind as TypeKind, Nullability},
+    read_rel::ReadType,
+    rel::RelType,
+    Expression, NamedStruct, Plan, Rel, Type,
+};
+
+/// Reconstruct a [`LogicalPlan`] from a Substrait [`Plan`].
+pub fn from_substrait_plan(plan: &Plan) -> Result<LogicalPlan> {
+    // Build the anchor -> function-name table from the plan's extension declarations so that
+    // function references can be resolved back to names while walking the relations.
+    let mut functions = HashMap::new();
+    for ext in &plan.extensions {
+        if let Some(MappingType::ExtensionFunction(f)) = &ext.mapping_type {
+            functions.insert(f.function_anchor, f.name.clone());
+        }
+    }
+
+    let rel = plan
+        .relations
+        .first()
+        .and_then(|r| r.rel_type.as_ref())
+        .ok_or_else(|| {
+            ExecutionError::General("Substrait plan has no relations".to_string())
+        })?;
+
+    match rel {
+        PlanRelType::Rel(rel) => from_substrait_rel(rel, &functions),
+        PlanRelType::Root(root) => {
+            let input = root.input.as_ref().ok_or_else(|| {
+                ExecutionError::General("Substrait root has no input".to_string())
+            })?;
+            from_substrait_rel(input, &functions)
+        }
+    }
+}
+
+/// Recursively translate a Substrait [`Rel`] into a [`LogicalPlan`].
+fn from_substrait_rel(rel: &Rel, functions: &HashMap<u32, String>) -> Result<LogicalPlan> {
+    match rel.rel_type.as_ref() {
+        Some(RelType::Read(read)) => match read.read_type.as_ref() {
+            Some(ReadType::NamedTable(nt)) => {
+                let name = nt.names.first().cloned().unwrap_or_default();
+                let fields = read
+                    .base_schema
+                    .as_ref()
+                    .map(from_substrait_named_struct)
+                    .transpose()?
+                    .unwrap_or_default();
+                let projection = read.projection.as_ref().and_then(|mask| {
+                    mask.select.as_ref().map(|select| {
+                        select
+                            .struct_items
+                            .iter()
+                            .map(|item| item.field as usize)
+                            .collect::<Vec<_>>()
+                    })
+                });
+                LogicalPlanBuilder::scan_empty(&name, &fields, projection)?.build()
+            }
+            other => Err(ExecutionError::NotImplemented(format!(
+                "Unsupported Substrait read type: {:?}",
+                other
+            ))),
+        },
+        Some(RelType::Project(project)) => {
+            let input = required_input(&project.input, functions)?;
+            let in_schema = input.schema();
+            let expr = project
+                .expressions
+                .iter()
+                .map(|e| from_substrait_rex(e, in_schema, functions))
+                .collect::<Result<Vec<_>>>()?;
+            LogicalPlanBuilder::from(&input).project(expr)?.build()
+        }
+        Some(RelType::Filter(filter)) => {
+            let input = required_input(&filter.input, functions)?;
+            let in_schema = input.schema();
+            let predicate = from_substrait_rex(
+                filter.condition.as_ref().ok_or_else(|| {
+                    ExecutionError::General("Filter has no condition".to_string())
+                })?,
+                in_schema,
+                functions,
+            )?;
+            LogicalPlanBuilder::from(&input).filter(predicate)?.build()
+        }
+        Some(RelType::Aggregate(agg)) => {
+            let input = required_input(&agg.input, functions)?;
+            let in_schema = input.schema();
+            let group_expr = agg
+                .groupings
+                .first()
+                .map(|g| {
+                    g.grouping_expressions
+                        .iter()
+                        .map(|e| from_substrait_rex(e, in_schema, functions))
+                        .collect::<Result<Vec<_>>>()
+                })
+                .transpose()?
+                .unwrap_or_default();
+            let aggr_expr = agg
+                .measures
+                .iter()
+                .map(|m| from_substrait_measure(m, in_schema, functions))
+                .collect::<Result<Vec<_>>>()?;
+            LogicalPlanBuilder::from(&input)
+                .aggregate(group_expr, aggr_expr)?
+                .build()
+        }
+        Some(RelType::Sort(sort)) => {
+            let input = required_input(&sort.input, functions)?;
+            let in_schema = input.schema();
+            let expr = sort
+                .sorts
+                .iter()
+                .map(|s| from_substrait_sort(s, in_schema, functions))
+                .collect::<Result<Vec<_>>>()?;
+            LogicalPlanBuilder::from(&input).sort(expr)?.build()
+        }
+        Some(RelType::Fetch(fetch)) => {
+            let input = required_input(&fetch.input, functions)?;
+            // A Substrait fetch with no explicit count (0) means "unbounded"; preserve it as the
+            // largest representable limit rather than truncating the result to zero rows.
+            let n = if fetch.count <= 0 {
+                usize::MAX
+            } else {
+                fetch.count as usize
+            };
+            LogicalPlanBuilder::from(&input).limit(n)?.build()
+        }
+        Some(RelType::Join(join)) => {
+            let left = required_input(&join.left, functions)?;
+            let right = required_input(&join.right, functions)?;
+            let join_type = from_substrait_jointype(join.r#type)?;
+            let on = join
+                .expression
+                .as_ref()
+                .map(|e| from_substrait_join_condition(e, left.schema(), right.schema(), functions))
+                .transpose()?
+                .unwrap_or_default();
+            let left_cols = on.iter().map(|(l, _)| l.as_str()).collect::<Vec<_>>();
+            let right_cols = on.iter().map(|(_, r)| r.as_str()).collect::<Vec<_>>();
+            LogicalPlanBuilder::from(&left)
+                .join(&right, join_type, &left_cols, &right_cols)?
+                .build()
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported Substrait relation: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Unwrap and translate a boxed optional child relation.
+fn required_input(
+    input: &Option<Box<Rel>>,
+    functions: &HashMap<u32, String>,
+) -> Result<LogicalPlan> {
+    let rel = input
+        .as_ref()
+        .ok_or_else(|| ExecutionError::General("Missing input relation".to_string()))?;
+    from_substrait_rel(rel, functions)
+}
+
+/// Map a Substrait join type value back to a DataFusion [`JoinType`].
+fn from_substrait_jointype(value: i32) -> Result<JoinType> {
+    use substrait::protobuf::join_rel::JoinType as S;
+    match S::from_i32(value) {
+        Some(S::Inner) => Ok(JoinType::Inner),
+        Some(S::Left) => Ok(JoinType::Left),
+        Some(S::Right) => Ok(JoinType::Right),
+        Some(S::Outer) => Ok(JoinType::Full),
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported Substrait join type: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Translate a Substrait expression back into a DataFusion [`Expr`]. `schema` is the schema the
+/// expression was encoded against, used to resolve struct-field indices back to column names.
+fn from_substrait_rex(
+    expr: &Expression,
+    schema: &Schema,
+    functions: &HashMap<u32, String>,
+) -> Result<Expr> {
+    match expr.rex_type.as_ref() {
+        Some(RexType::Selection(field)) => {
+            let index = field_index(field) as usize;
+            let name = schema
+                .field(index)
+                .ok_or_else(|| {
+                    ExecutionError::General(format!(
+                        "Column index {} out of bounds for schema {:?}",
+                        index, schema
+                    ))
+                })?
+                .name()
+                .clone();
+            Ok(Expr::Column(name))
+        }
+        Some(RexType::Literal(lit)) => Ok(Expr::Literal(literal_to_scalar(lit))),
+        Some(RexType::ScalarFunction(f)) => {
+            let op = function_operator(&resolve(functions, f.function_reference))?;
+            let mut args = f.arguments.iter().filter_map(|a| match &a.arg_type {
+                Some(ArgType::Value(v)) => Some(v),
+                _ => None,
+            });
+            let left = args.next().ok_or_else(|| {
+                ExecutionError::General("Binary function missing left arg".to_string())
+            })?;
+            let right = args.next().ok_or_else(|| {
+                ExecutionError::General("Binary function missing right arg".to_string())
+            })?;
+            Ok(Expr::BinaryExpr {
+                left: Box::new(from_substrait_rex(left, schema, functions)?),
+                op,
+                right: Box::new(from_substrait_rex(right, schema, functions)?),
+            })
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported Substrait expression: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Translate a Substrait aggregate measure into a DataFusion aggregate [`Expr`].
+fn from_substrait_measure(
+    measure: &substrait::protobuf::aggregate_rel::Measure,
+    schema: &Schema,
+    functions: &HashMap<u32, String>,
+) -> Result<Expr> {
+    let func = measure.measure.as_ref().ok_or_else(|| {
+        ExecutionError::General("Aggregate measure has no function".to_string())
+    })?;
+    let name = resolve(functions, func.function_reference);
+    let args = func
+        .arguments
+        .iter()
+        .filter_map(|a| match &a.arg_type {
+            Some(ArgType::Value(v)) => Some(from_substrait_rex(v, schema, functions)),
+            _ => None,
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Expr::AggregateFunction {
+        name: name.to_uppercase(),
+        args,
+    })
+}
+
+/// Translate a Substrait sort field into a DataFusion sort [`Expr`].
+fn from_substrait_sort(
+    sort: &substrait::protobuf::sort_field::SortField,
+    schema: &Schema,
+    functions: &HashMap<u32, String>,
+) -> Result<Expr> {
+    use substrait::protobuf::sort_field::{SortDirection, SortKind};
+    let expr = sort
+        .expr
+        .as_ref()
+        .ok_or_else(|| ExecutionError::General("Sort field has no expr".to_string()))?;
+    let (asc, nulls_first) = match &sort.sort_kind {
+        Some(SortKind::Direction(d)) => match SortDirection::from_i32(*d) {
+            Some(SortDirection::AscNullsFirst) => (true, true),
+            Some(SortDirection::AscNullsLast) => (true, false),
+            Some(SortDirection::DescNullsFirst) => (false, true),
+            Some(SortDirection::DescNullsLast) => (false, false),
+            _ => (true, false),
+        },
+        _ => (true, false),
+    };
+    Ok(Expr::Sort {
+        expr: Box::new(from_substrait_rex(expr, schema, functions)?),
+        asc,
+        nulls_first,
+    })
+}
+
+/// Resolve a function anchor against the extension table, falling back to the raw id as text.
+fn resolve(functions: &HashMap<u32, String>, anchor: u32) -> String {
+    functions
+        .get(&anchor)
+        .cloned()
+        .unwrap_or_else(|| anchor.to_string())
+}
+
+/// Map a canonical Substrait function name back to a DataFusion [`Operator`].
+fn function_operator(name: &str) -> Result<Operator> {
+    Ok(match name {
+        "equal" => Operator::Eq,
+        "not_equal" => Operator::NotEq,
+        "lt" => Operator::Lt,
+        "lte" => Operator::LtEq,
+        "gt" => Operator::Gt,
+        "gte" => Operator::GtEq,
+        "add" => Operator::Plus,
+        "subtract" => Operator::Minus,
+        "multiply" => Operator::Multiply,
+        "divide" => Operator::Divide,
+        "and" => Operator::And,
+        "or" => Operator::Or,
+        other => {
+            return Err(ExecutionError::NotImplemented(format!(
+                "Unsupported Substrait operator: {}",
+                other
+            )))
+        }
+    })
+}
+
+/// Recover the equi-join column-name pairs from a Substrait join condition. Only a single
+/// equality or an AND-chain of equalities between direct column references is supported, which
+/// is exactly what the producer emits for a DataFusion equi-join.
+fn from_substrait_join_condition(
+    expr: &Expression,
+    left_schema: &Schema,
+    right_schema: &Schema,
+    functions: &HashMap<u32, String>,
+) -> Result<Vec<(String, String)>> {
+    let combined = Schema::new(
+        left_schema
+            .fields()
+            .iter()
+            .chain(right_schema.fields().iter())
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+    let mut pairs = Vec::new();
+    collect_join_equalities(expr, &combined, left_schema.fields().len(), functions, &mut pairs)?;
+    Ok(pairs)
+}
+
+/// Walk an AND-chain of equalities, pushing each `(left_name, right_name)` pair onto `pairs` in
+/// left-then-right order regardless of which side of the `=` each operand appeared on.
+fn collect_join_equalities(
+    expr: &Expression,
+    combined: &Schema,
+    left_len: usize,
+    functions: &HashMap<u32, String>,
+    pairs: &mut Vec<(String, String)>,
+) -> Result<()> {
+    if let Some(RexType::ScalarFunction(f)) = expr.rex_type.as_ref() {
+        let name = resolve(functions, f.function_reference);
+        let mut args = f.arguments.iter().filter_map(|a| match &a.arg_type {
+            Some(ArgType::Value(v)) => Some(v),
+            _ => None,
+        });
+        if let (Some(left), Some(right)) = (args.next(), args.next()) {
+            if name == "and" {
+                collect_join_equalities(left, combined, left_len, functions, pairs)?;
+                collect_join_equalities(right, combined, left_len, functions, pairs)?;
+                return Ok(());
+            }
+            if name == "equal" {
+                let li = as_selection_index(left)?;
+                let ri = as_selection_index(right)?;
+                let (li, ri) = if (li as usize) < left_len { (li, ri) } else { (ri, li) };
+                let field_name = |index: usize| -> Result<String> {
+                    combined
+                        .field(index)
+                        .map(|f| f.name().clone())
+                        .ok_or_else(|| {
+                            ExecutionError::General(format!(
+                                "Column index {} out of bounds for schema {:?}",
+                                index, combined
+                            ))
+                        })
+                };
+                pairs.push((field_name(li as usize)?, field_name(ri as usize)?));
+                return Ok(());
+            }
+        }
+    }
+    Err(ExecutionError::NotImplemented(format!(
+        "Unsupported join condition: {:?}",
+        expr
+    )))
+}
+
+/// Extract the struct-field index of an expression expected to be a direct column reference.
+fn as_selection_index(expr: &Expression) -> Result<i32> {
+    match expr.rex_type.as_ref() {
+        Some(RexType::Selection(field)) => Ok(field_index(field)),
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Expected a column reference in join condition, found: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Extract the leading struct-field index from a direct field reference.
+fn field_index(field: &substrait::protobuf::expression::FieldReference) -> i32 {
+    use substrait::protobuf::expression::{
+        field_reference::ReferenceType, reference_segment,
+    };
+    if let Some(ReferenceType::DirectReference(segment)) = &field.reference_type {
+        if let Some(reference_segment::ReferenceType::StructField(sf)) =
+            &segment.reference_type
+        {
+            return sf.field;
+        }
+    }
+    0
+}
+
+/// Convert a Substrait literal into a DataFusion scalar value.
+fn literal_to_scalar(lit: &substrait::protobuf::expression::Literal) -> ScalarValue {
+    match &lit.literal_type {
+        Some(LiteralType::Boolean(b)) => ScalarValue::Boolean(Some(*b)),
+        Some(LiteralType::I64(i)) => ScalarValue::Int64(Some(*i)),
+        Some(LiteralType::Fp64(f)) => ScalarValue::Float64(Some(*f)),
+        Some(LiteralType::String(s)) => ScalarValue::Utf8(Some(s.clone())),
+        _ => ScalarValue::Null,
+    }
+}
+
+/// Reconstruct a scan's Arrow fields from a Substrait `NamedStruct`, the reverse of
+/// `to_substrait_named_struct` in the producer.
+fn from_substrait_named_struct(named: &NamedStruct) -> Result<Vec<Field>> {
+    let types = named
+        .r#struct
+        .as_ref()
+        .map(|s| s.types.as_slice())
+        .unwrap_or(&[]);
+    named
+        .names
+        .iter()
+        .zip(types.iter())
+        .map(|(name, ty)| {
+            let (data_type, nullable) = from_substrait_type(ty)?;
+            Ok(Field::new(name, data_type, nullable))
+        })
+        .collect()
+}
+
+/// Map a Substrait [`Type`] back onto an Arrow `DataType`, matching the scalar types
+/// `literal_to_scalar` already knows how to translate.
+fn from_substrait_type(ty: &Type) -> Result<(DataType, bool)> {
+    match ty.kind.as_ref() {
+        Some(TypeKind::Bool(b)) => Ok((DataType::Boolean, is_nullable(b.nullability))),
+        Some(TypeKind::I64(i)) => Ok((DataType::Int64, is_nullable(i.nullability))),
+        Some(TypeKind::Fp64(f)) => Ok((DataType::Float64, is_nullable(f.nullability))),
+        Some(TypeKind::String(s)) => Ok((DataType::Utf8, is_nullable(s.nullability))),
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported Substrait type in consumer: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Whether a Substrait `Nullability` value means the field accepts nulls.
+fn is_nullable(nullability: i32) -> bool {
+    nullability != Nullability::Required as i32
+}
@@ -0,0 +1,108 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serialization of a [`LogicalPlan`](crate::logicalplan::LogicalPlan) to and from the
+//! [Substrait](https://substrait.io) cross-language plan format.
+//!
+//! The [`producer`] walks the logical plan tree and emits a Substrait `Plan` message; the
+//! [`consumer`] reconstructs the equivalent logical plan from such a message. Together they let a
+//! DataFrame pipeline built in process be shipped to another engine or persisted and replayed
+//! later.
+
+pub mod consumer;
+pub mod producer;
+
+pub use consumer::from_substrait_plan;
+pub use producer::to_substrait_plan;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::logicalplan::{col, Expr, JoinType, LogicalPlanBuilder};
+    use arrow::datatypes::{DataType, Field};
+
+    /// Assert that a logical plan survives a round-trip through Substrait unchanged.
+    fn roundtrip(plan: &crate::logicalplan::LogicalPlan) -> Result<()> {
+        let proto = to_substrait_plan(plan)?;
+        let back = from_substrait_plan(&proto)?;
+        assert_eq!(format!("{:?}", plan), format!("{:?}", back));
+        Ok(())
+    }
+
+    /// A two-column schema (`a`, `b`) used to exercise name-based column resolution.
+    fn test_schema() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, false),
+        ]
+    }
+
+    #[test]
+    fn roundtrip_table_scan() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty("t", &[], None)?.build()?;
+        roundtrip(&plan)
+    }
+
+    #[test]
+    fn roundtrip_projection() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty("t", &test_schema(), None)?
+            .project(vec![col("b"), col("a")])?
+            .build()?;
+        roundtrip(&plan)
+    }
+
+    #[test]
+    fn roundtrip_filter() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty("t", &test_schema(), None)?
+            .filter(col("a").eq(col("b")))?
+            .build()?;
+        roundtrip(&plan)
+    }
+
+    #[test]
+    fn roundtrip_aggregate() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty("t", &test_schema(), None)?
+            .aggregate(
+                vec![col("a")],
+                vec![Expr::AggregateFunction {
+                    name: "MIN".to_string(),
+                    args: vec![col("b")],
+                }],
+            )?
+            .build()?;
+        roundtrip(&plan)
+    }
+
+    #[test]
+    fn roundtrip_sort() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty("t", &test_schema(), None)?
+            .sort(vec![col("b").sort(true, true), col("a").sort(false, false)])?
+            .build()?;
+        roundtrip(&plan)
+    }
+
+    #[test]
+    fn roundtrip_join() -> Result<()> {
+        let left = LogicalPlanBuilder::scan_empty("l", &test_schema(), None)?.build()?;
+        let right = LogicalPlanBuilder::scan_empty("r", &test_schema(), None)?.build()?;
+        let plan = LogicalPlanBuilder::from(&left)
+            .join(&right, JoinType::Inner, &["a"], &["b"])?
+            .build()?;
+        roundtrip(&plan)
+    }
+}
@@ -0,0 +1,514 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Produce a Substrait `Plan` from a DataFusion [`LogicalPlan`].
+
+use std::collections::HashMap;
+
+use arrow::datatypes::{DataType, Field, Schema};
+
+use crate::error::{ExecutionError, Result};
+use crate::logicalplan::{Expr, LogicalPlan, Operator};
+
+use substrait::protobuf::{
+    expression::{
+        field_reference::ReferenceType, literal::LiteralType, reference_segment,
+        FieldReference, Literal, ReferenceSegment, RexType, ScalarFunction,
+    },
+    extensions::{
+        simple_extension_declaration::{ExtensionFunction, MappingType},
+        SimpleExtensionDeclaration,
+    },
+    function_argument::ArgType,
+    plan_rel::RelType as PlanRelType,
+    r#type::{
+        Boolean as TypeBoolean, Fp64 as TypeFp64, Kind as TypeKind, Nullability,
+        String as TypeString, Struct as TypeStruct, I64 as TypeI64,
+    },
+    read_rel::{NamedTable, ReadType},
+    rel::RelType,
+    AggregateFunction, AggregateRel, Expression, FetchRel, FilterRel, FunctionArgument,
+    JoinRel, NamedStruct, Plan, PlanRel, ProjectRel, ReadRel, Rel, SortRel, Type,
+};
+
+/// Registry that assigns a stable anchor id to each scalar or aggregate function name so that a
+/// function is declared once in the plan's extension table and referenced by id everywhere else.
+#[derive(Default)]
+struct ExtensionRegistry {
+    functions: HashMap<String, u32>,
+}
+
+impl ExtensionRegistry {
+    /// Return the anchor for `name`, registering it on first use.
+    fn register(&mut self, name: &str) -> u32 {
+        let next = self.functions.len() as u32;
+        *self.functions.entry(name.to_owned()).or_insert(next)
+    }
+
+    /// Emit the collected declarations, ordered by anchor.
+    fn declarations(&self) -> Vec<SimpleExtensionDeclaration> {
+        let mut entries: Vec<(&String, &u32)> = self.functions.iter().collect();
+        entries.sort_by_key(|(_, anchor)| **anchor);
+        entries
+            .into_iter()
+            .map(|(name, anchor)| SimpleExtensionDeclaration {
+                mapping_type: Some(MappingType::ExtensionFunction(ExtensionFunction {
+                    extension_uri_reference: 0,
+                    function_anchor: *anchor,
+                    name: name.clone(),
+                })),
+            })
+            .collect()
+    }
+}
+
+/// Convert a DataFusion [`LogicalPlan`] into a Substrait [`Plan`].
+pub fn to_substrait_plan(plan: &LogicalPlan) -> Result<Plan> {
+    let mut registry = ExtensionRegistry::default();
+    let rel = to_substrait_rel(plan, &mut registry)?;
+    Ok(Plan {
+        extension_uris: vec![],
+        extensions: registry.declarations(),
+        relations: vec![PlanRel {
+            rel_type: Some(PlanRelType::Rel(rel)),
+        }],
+        ..Default::default()
+    })
+}
+
+/// Translate a single logical plan node (recursing into its inputs) to a Substrait [`Rel`].
+fn to_substrait_rel(plan: &LogicalPlan, registry: &mut ExtensionRegistry) -> Result<Rel> {
+    match plan {
+        LogicalPlan::TableScan {
+            table_name,
+            schema,
+            projection,
+            ..
+        } => {
+            let base_schema = to_substrait_named_struct(schema)?;
+            let projection = projection.as_ref().map(|cols| {
+                let fields = cols
+                    .iter()
+                    .map(|i| substrait::protobuf::expression::reference_segment::StructField {
+                        field: *i as i32,
+                        child: None,
+                    })
+                    .map(|sf| reference_segment::ReferenceType::StructField(Box::new(sf)))
+                    .map(|rt| ReferenceSegment {
+                        reference_type: Some(rt),
+                    })
+                    .collect();
+                substrait::protobuf::expression::MaskExpression {
+                    select: Some(substrait::protobuf::expression::mask_expression::StructSelect {
+                        struct_items: fields,
+                    }),
+                    maintain_singular_struct: false,
+                }
+            });
+            Ok(Rel {
+                rel_type: Some(RelType::Read(Box::new(ReadRel {
+                    base_schema: Some(base_schema),
+                    projection,
+                    read_type: Some(ReadType::NamedTable(NamedTable {
+                        names: vec![table_name.clone()],
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }))),
+            })
+        }
+        LogicalPlan::Projection { expr, input, .. } => {
+            let in_schema = input.schema();
+            let input = to_substrait_rel(input, registry)?;
+            let expressions = expr
+                .iter()
+                .map(|e| to_substrait_rex(e, in_schema, registry))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Rel {
+                rel_type: Some(RelType::Project(Box::new(ProjectRel {
+                    input: Some(Box::new(input)),
+                    expressions,
+                    ..Default::default()
+                }))),
+            })
+        }
+        LogicalPlan::Filter { predicate, input } => {
+            let in_schema = input.schema();
+            let input = to_substrait_rel(input, registry)?;
+            let condition = to_substrait_rex(predicate, in_schema, registry)?;
+            Ok(Rel {
+                rel_type: Some(RelType::Filter(Box::new(FilterRel {
+                    input: Some(Box::new(input)),
+                    condition: Some(Box::new(condition)),
+                    ..Default::default()
+                }))),
+            })
+        }
+        LogicalPlan::Aggregate {
+            input,
+            group_expr,
+            aggr_expr,
+            ..
+        } => {
+            let in_schema = input.schema();
+            let input = to_substrait_rel(input, registry)?;
+            let groupings = group_expr
+                .iter()
+                .map(|e| to_substrait_rex(e, in_schema, registry))
+                .collect::<Result<Vec<_>>>()?;
+            let measures = aggr_expr
+                .iter()
+                .map(|e| to_substrait_agg_measure(e, in_schema, registry))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Rel {
+                rel_type: Some(RelType::Aggregate(Box::new(AggregateRel {
+                    input: Some(Box::new(input)),
+                    groupings: vec![substrait::protobuf::aggregate_rel::Grouping {
+                        grouping_expressions: groupings,
+                    }],
+                    measures,
+                    ..Default::default()
+                }))),
+            })
+        }
+        LogicalPlan::Sort { expr, input } => {
+            let in_schema = input.schema();
+            let input = to_substrait_rel(input, registry)?;
+            let sorts = expr
+                .iter()
+                .map(|e| to_substrait_sort_field(e, in_schema, registry))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Rel {
+                rel_type: Some(RelType::Sort(Box::new(SortRel {
+                    input: Some(Box::new(input)),
+                    sorts,
+                    ..Default::default()
+                }))),
+            })
+        }
+        LogicalPlan::Limit { n, input } => {
+            let input = to_substrait_rel(input, registry)?;
+            Ok(Rel {
+                rel_type: Some(RelType::Fetch(Box::new(FetchRel {
+                    input: Some(Box::new(input)),
+                    offset: 0,
+                    count: *n as i64,
+                    ..Default::default()
+                }))),
+            })
+        }
+        LogicalPlan::Join {
+            left,
+            right,
+            on,
+            join_type,
+            ..
+        } => {
+            let left_schema = left.schema();
+            let right_schema = right.schema();
+            let expression = to_substrait_join_condition(on, left_schema, right_schema, registry)?;
+            let left = to_substrait_rel(left, registry)?;
+            let right = to_substrait_rel(right, registry)?;
+            Ok(Rel {
+                rel_type: Some(RelType::Join(Box::new(JoinRel {
+                    left: Some(Box::new(left)),
+                    right: Some(Box::new(right)),
+                    r#type: to_substrait_jointype(join_type) as i32,
+                    expression: expression.map(Box::new),
+                    ..Default::default()
+                }))),
+            })
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported plan node in Substrait producer: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Map a DataFusion join type onto the Substrait `JoinType` enum value.
+fn to_substrait_jointype(join_type: &crate::logicalplan::JoinType) -> substrait::protobuf::join_rel::JoinType {
+    use crate::logicalplan::JoinType;
+    use substrait::protobuf::join_rel::JoinType as S;
+    match join_type {
+        JoinType::Inner => S::Inner,
+        JoinType::Left => S::Left,
+        JoinType::Right => S::Right,
+        JoinType::Full => S::Outer,
+    }
+}
+
+/// Build the Substrait join condition (an AND-chain of equalities) from the DataFusion
+/// equi-join keys, resolving each column against the schema formed by concatenating the left
+/// and right input schemas, which is how Substrait numbers fields in a join's output.
+fn to_substrait_join_condition(
+    on: &[(String, String)],
+    left_schema: &Schema,
+    right_schema: &Schema,
+    registry: &mut ExtensionRegistry,
+) -> Result<Option<Expression>> {
+    if on.is_empty() {
+        return Ok(None);
+    }
+    // Fields are qualified with their side so that a right key resolves to its slot after the
+    // left fields even when the left input has a column of the same name.
+    let qualify = |side: &str, f: &Field| {
+        Field::new(&format!("{}.{}", side, f.name()), f.data_type().clone(), f.is_nullable())
+    };
+    let combined = Schema::new(
+        left_schema
+            .fields()
+            .iter()
+            .map(|f| qualify("left", f))
+            .chain(right_schema.fields().iter().map(|f| qualify("right", f)))
+            .collect::<Vec<_>>(),
+    );
+    let mut condition: Option<Expr> = None;
+    for (left_col, right_col) in on {
+        let eq = Expr::BinaryExpr {
+            left: Box::new(Expr::Column(format!("left.{}", left_col))),
+            op: Operator::Eq,
+            right: Box::new(Expr::Column(format!("right.{}", right_col))),
+        };
+        condition = Some(match condition {
+            None => eq,
+            Some(prev) => Expr::BinaryExpr {
+                left: Box::new(prev),
+                op: Operator::And,
+                right: Box::new(eq),
+            },
+        });
+    }
+    // unwrap: `on` is non-empty, so `condition` was assigned at least once above.
+    Ok(Some(to_substrait_rex(
+        &condition.unwrap(),
+        &combined,
+        registry,
+    )?))
+}
+
+/// Translate a scalar expression into a Substrait expression ("Rex"). `schema` is the schema
+/// `expr` is evaluated against, used to resolve [`Expr::Column`] names to struct-field indices.
+fn to_substrait_rex(expr: &Expr, schema: &Schema, registry: &mut ExtensionRegistry) -> Result<Expression> {
+    match expr {
+        Expr::Column(name) => {
+            let index = schema.index_of(name).map_err(|_| {
+                ExecutionError::General(format!(
+                    "Column '{}' not found in schema {:?}",
+                    name, schema
+                ))
+            })?;
+            Ok(Expression {
+                rex_type: Some(RexType::Selection(Box::new(FieldReference {
+                    reference_type: Some(ReferenceType::DirectReference(ReferenceSegment {
+                        reference_type: Some(reference_segment::ReferenceType::StructField(
+                            Box::new(reference_segment::StructField {
+                                field: index as i32,
+                                child: None,
+                            }),
+                        )),
+                    })),
+                    ..Default::default()
+                }))),
+            })
+        }
+        Expr::Literal(value) => Ok(Expression {
+            rex_type: Some(RexType::Literal(scalar_to_literal(value))),
+        }),
+        Expr::BinaryExpr { left, op, right } => {
+            let anchor = registry.register(operator_name(op)?);
+            let args = vec![
+                FunctionArgument {
+                    arg_type: Some(ArgType::Value(to_substrait_rex(left, schema, registry)?)),
+                },
+                FunctionArgument {
+                    arg_type: Some(ArgType::Value(to_substrait_rex(right, schema, registry)?)),
+                },
+            ];
+            Ok(Expression {
+                rex_type: Some(RexType::ScalarFunction(ScalarFunction {
+                    function_reference: anchor,
+                    arguments: args,
+                    ..Default::default()
+                })),
+            })
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Unsupported expression in Substrait producer: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Translate an aggregate expression into a Substrait measure, registering the function name.
+fn to_substrait_agg_measure(
+    expr: &Expr,
+    schema: &Schema,
+    registry: &mut ExtensionRegistry,
+) -> Result<substrait::protobuf::aggregate_rel::Measure> {
+    match expr {
+        Expr::AggregateFunction { name, args, .. } => {
+            let anchor = registry.register(&name.to_lowercase());
+            let arguments = args
+                .iter()
+                .map(|a| {
+                    Ok(FunctionArgument {
+                        arg_type: Some(ArgType::Value(to_substrait_rex(a, schema, registry)?)),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(substrait::protobuf::aggregate_rel::Measure {
+                measure: Some(AggregateFunction {
+                    function_reference: anchor,
+                    arguments,
+                    ..Default::default()
+                }),
+                filter: None,
+            })
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Expected an aggregate expression, found: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Translate a sort expression into a Substrait sort field.
+fn to_substrait_sort_field(
+    expr: &Expr,
+    schema: &Schema,
+    registry: &mut ExtensionRegistry,
+) -> Result<substrait::protobuf::sort_field::SortField> {
+    match expr {
+        Expr::Sort {
+            expr,
+            asc,
+            nulls_first,
+        } => {
+            use substrait::protobuf::sort_field::{SortDirection, SortKind};
+            let direction = match (asc, nulls_first) {
+                (true, true) => SortDirection::AscNullsFirst,
+                (true, false) => SortDirection::AscNullsLast,
+                (false, true) => SortDirection::DescNullsFirst,
+                (false, false) => SortDirection::DescNullsLast,
+            };
+            Ok(substrait::protobuf::sort_field::SortField {
+                expr: Some(to_substrait_rex(expr, schema, registry)?),
+                sort_kind: Some(SortKind::Direction(direction as i32)),
+            })
+        }
+        other => Err(ExecutionError::NotImplemented(format!(
+            "Expected a sort expression, found: {:?}",
+            other
+        ))),
+    }
+}
+
+/// Map a DataFusion [`Operator`] to the canonical Substrait function name.
+fn operator_name(op: &Operator) -> Result<&'static str> {
+    Ok(match op {
+        Operator::Eq => "equal",
+        Operator::NotEq => "not_equal",
+        Operator::Lt => "lt",
+        Operator::LtEq => "lte",
+        Operator::Gt => "gt",
+        Operator::GtEq => "gte",
+        Operator::Plus => "add",
+        Operator::Minus => "subtract",
+        Operator::Multiply => "multiply",
+        Operator::Divide => "divide",
+        Operator::And => "and",
+        Operator::Or => "or",
+        other => {
+            return Err(ExecutionError::NotImplemented(format!(
+                "Unsupported operator in Substrait producer: {:?}",
+                other
+            )))
+        }
+    })
+}
+
+/// Build the Substrait `NamedStruct` describing a scan's full table schema, so that the consumer
+/// can reconstruct the scan's columns instead of starting from an empty schema.
+fn to_substrait_named_struct(schema: &Schema) -> Result<NamedStruct> {
+    let names = schema.fields().iter().map(|f| f.name().clone()).collect();
+    let types = schema
+        .fields()
+        .iter()
+        .map(|f| to_substrait_type(f.data_type(), f.is_nullable()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(NamedStruct {
+        names,
+        r#struct: Some(TypeStruct {
+            types,
+            nullability: Nullability::Required as i32,
+            ..Default::default()
+        }),
+    })
+}
+
+/// Map an Arrow [`DataType`] onto the Substrait [`Type`] used in a [`NamedStruct`], matching the
+/// scalar types [`scalar_to_literal`] already knows how to translate.
+fn to_substrait_type(data_type: &DataType, nullable: bool) -> Result<Type> {
+    let nullability = if nullable {
+        Nullability::Nullable
+    } else {
+        Nullability::Required
+    } as i32;
+    let kind = match data_type {
+        DataType::Boolean => TypeKind::Bool(TypeBoolean {
+            nullability,
+            ..Default::default()
+        }),
+        DataType::Int64 => TypeKind::I64(TypeI64 {
+            nullability,
+            ..Default::default()
+        }),
+        DataType::Float64 => TypeKind::Fp64(TypeFp64 {
+            nullability,
+            ..Default::default()
+        }),
+        DataType::Utf8 => TypeKind::String(TypeString {
+            nullability,
+            ..Default::default()
+        }),
+        other => {
+            return Err(ExecutionError::NotImplemented(format!(
+                "Unsupported Arrow type in Substrait producer: {:?}",
+                other
+            )))
+        }
+    };
+    Ok(Type { kind: Some(kind) })
+}
+
+/// Convert a DataFusion scalar value into a Substrait literal.
+fn scalar_to_literal(value: &crate::logicalplan::ScalarValue) -> Literal {
+    use crate::logicalplan::ScalarValue;
+    let literal_type = match value {
+        ScalarValue::Boolean(Some(b)) => Some(LiteralType::Boolean(*b)),
+        ScalarValue::Int64(Some(i)) => Some(LiteralType::I64(*i)),
+        ScalarValue::Float64(Some(f)) => Some(LiteralType::Fp64(*f)),
+        ScalarValue::Utf8(Some(s)) => Some(LiteralType::String(s.clone())),
+        _ => None,
+    };
+    Literal {
+        literal_type,
+        nullable: true,
+        ..Default::default()
+    }
+}